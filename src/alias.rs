@@ -0,0 +1,102 @@
+//! User-configured shorthand commands, e.g. `rm = uninstall --all` or
+//! `up = update -j 8` under an `aliases:` section of
+//! `PACK_CONFIG_DIR/config`, analogous to cargo's config-driven aliases.
+//!
+//! The top-level command dispatcher is expected to call `resolve()` on the
+//! raw argv before falling back to its built-in subcommand table, so that
+//! built-ins always take precedence over a same-named alias.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+
+use package::PACK_CONFIG_DIR;
+use yaml_rust::{Yaml, YamlLoader};
+
+lazy_static! {
+    static ref ALIAS_FILE: PathBuf = (*PACK_CONFIG_DIR).join("config");
+}
+
+/// Expand `argv[0]` through the user's alias table until it names a
+/// built-in subcommand (as reported by `is_builtin`) or no further alias
+/// applies. `argv[0]` is replaced by its expansion and any trailing
+/// arguments are preserved after it.
+///
+/// Dies loudly if an alias expands back into itself, directly or through
+/// a chain of other aliases.
+pub fn resolve<F>(argv: Vec<String>, is_builtin: F) -> Vec<String>
+    where F: Fn(&str) -> bool
+{
+    let aliases = match load() {
+        Some(aliases) => aliases,
+        None => return argv,
+    };
+
+    let mut argv = argv;
+    let mut seen: Vec<String> = Vec::new();
+
+    loop {
+        let cmd = match argv.first() {
+            Some(cmd) => cmd.clone(),
+            None => return argv,
+        };
+
+        if is_builtin(&cmd) {
+            return argv;
+        }
+
+        let expansion = match aliases.iter().find(|&&(ref name, _)| *name == cmd) {
+            Some(&(_, ref expansion)) => expansion.clone(),
+            None => return argv,
+        };
+
+        if seen.contains(&cmd) {
+            die!("alias `{}` is recursive; check {}", cmd, ALIAS_FILE.display());
+        }
+        seen.push(cmd);
+
+        let rest = argv.split_off(1);
+        argv = expansion;
+        argv.extend(rest);
+    }
+}
+
+fn load() -> Option<Vec<(String, Vec<String>)>> {
+    if !ALIAS_FILE.is_file() {
+        return None;
+    }
+
+    let mut data = String::new();
+    File::open(&*ALIAS_FILE)
+        .expect("Fail to open config")
+        .read_to_string(&mut data)
+        .expect("Fail to read config");
+    let docs = YamlLoader::load_from_str(&data).expect("Unexpected config format");
+
+    if docs.is_empty() {
+        return None;
+    }
+
+    let hash = match docs[0]["aliases"].as_hash() {
+        Some(hash) => hash,
+        None => return None,
+    };
+
+    let mut aliases = Vec::new();
+    for (name, value) in hash {
+        let name = match name.as_str() {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+        let args = match *value {
+            Yaml::String(ref s) => s.split_whitespace().map(String::from).collect(),
+            Yaml::Array(ref items) => {
+                items.iter().filter_map(|i| i.as_str().map(String::from)).collect()
+            }
+            _ => continue,
+        };
+        aliases.push((name, args));
+    }
+
+    Some(aliases)
+}
@@ -0,0 +1,25 @@
+use std::fs;
+
+use Result;
+use package::{self, Package};
+use git;
+
+/// Clone `pack` into place and record the resulting revision in the
+/// lockfile. Used by `pack sync` to install plugins missing from the
+/// manifest. Does nothing, and records nothing, if the plugin directory
+/// already exists.
+pub fn install_plugin(pack: &Package) -> Result<()> {
+    let path = pack.path();
+    if path.is_dir() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    git::clone(&pack.name, &path)?;
+
+    let rev = git::head(&path)?;
+    let mut locked = pack.clone();
+    locked.set_revision(&rev);
+    package::update_lock_entry(&locked)
+}
@@ -0,0 +1,4 @@
+pub mod install;
+pub mod sync;
+pub mod uninstall;
+pub mod update;
@@ -0,0 +1,105 @@
+use std::path::Path;
+
+use Result;
+use package::{self, Package};
+use cmd::install::install_plugin;
+use cmd::uninstall::uninstall_plugin;
+use num_cpus;
+use docopt::Docopt;
+use task::TaskManager;
+
+const USAGE: &str = "
+Reconcile installed plugins against a manifest file.
+
+Usage:
+    pack sync <manifest> [options]
+    pack sync -h | --help
+
+Options:
+    --prune                 Remove installed plugins missing from the manifest
+    --no-prune              Keep installed plugins missing from the manifest (default)
+    -n, --dry-run           Print the planned changes without touching anything
+    -j, --threads THREADS   Install missing plugins concurrently
+    -h, --help              Display this message
+";
+
+#[derive(Debug, RustcDecodable)]
+struct SyncArgs {
+    arg_manifest: String,
+    flag_prune: bool,
+    flag_no_prune: bool,
+    flag_dry_run: bool,
+    flag_threads: Option<usize>,
+}
+
+pub fn execute(args: &[String]) {
+    let mut argv = vec!["pack".to_string(), "sync".to_string()];
+    argv.extend_from_slice(args);
+
+    let args: SyncArgs =
+        Docopt::new(USAGE).and_then(|d| d.argv(argv).decode()).unwrap_or_else(|e| e.exit());
+
+    let prune = args.flag_prune && !args.flag_no_prune;
+    let threads = args.flag_threads.unwrap_or_else(num_cpus::get);
+
+    if let Err(e) = sync(&args.arg_manifest, prune, args.flag_dry_run, threads) {
+        die!("{}", e);
+    }
+}
+
+fn sync(manifest_path: &str, prune: bool, dry_run: bool, threads: usize) -> Result<()> {
+    let manifest = package::fetch_from(Path::new(manifest_path))?;
+    let installed = package::fetch().unwrap_or_else(Vec::new);
+
+    let to_add: Vec<Package> = manifest.iter()
+        .filter(|m| !installed.iter().any(|i| i.name == m.name))
+        .cloned()
+        .collect();
+    let to_remove: Vec<Package> = installed.iter()
+        .filter(|i| !manifest.iter().any(|m| m.name == i.name))
+        .cloned()
+        .collect();
+
+    if dry_run {
+        println!("Would install:");
+        for pack in &to_add {
+            println!("  + {}", pack.name);
+        }
+        println!("Would remove:");
+        for pack in &to_remove {
+            println!("  - {}", pack.name);
+        }
+        if !prune {
+            println!("(pass --prune to actually remove these)");
+        }
+        return Ok(());
+    }
+
+    let mut manager = TaskManager::new(threads);
+    for pack in &to_add {
+        manager.add(pack.clone());
+    }
+    let failed = manager.run(install_plugin);
+    for fail in &failed {
+        println!("Failed to install {}", fail);
+    }
+
+    if prune {
+        for pack in &to_remove {
+            uninstall_plugin(pack, false)?;
+        }
+    }
+
+    let mut result = manifest;
+    result.retain(|pack| !failed.contains(&pack.name));
+    if !prune {
+        for pack in to_remove {
+            result.push(pack);
+        }
+    }
+    result.sort_by(|a, b| a.name.cmp(&b.name));
+
+    package::update_pack_plugin(&result)?;
+    package::save(result)?;
+    Ok(())
+}
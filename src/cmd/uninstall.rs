@@ -2,6 +2,7 @@ use std::fs;
 
 use Result;
 use package::{self, Package};
+use suggest;
 use docopt::Docopt;
 
 const USAGE: &str = "
@@ -37,6 +38,8 @@ pub fn execute(args: &[String]) {
 fn uninstall_plugins(plugins: &[String], all: bool) -> Result<()> {
     let mut packs = package::fetch()?;
 
+    suggest::check_known(plugins, &packs);
+
     for pack in packs.iter().filter(|p| plugins.contains(&p.name)) {
         uninstall_plugin(pack, all)?;
     }
@@ -48,7 +51,7 @@ fn uninstall_plugins(plugins: &[String], all: bool) -> Result<()> {
     Ok(())
 }
 
-fn uninstall_plugin(plugin: &Package, all: bool) -> Result<()> {
+pub fn uninstall_plugin(plugin: &Package, all: bool) -> Result<()> {
     let config_file = plugin.config_path();
     let plugin_path = plugin.path();
 
@@ -1,10 +1,21 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use {Error, Result};
 use package::{self, Package};
+use suggest;
 use num_cpus;
 use docopt::Docopt;
 use git;
 use task::TaskManager;
 
+lazy_static! {
+    // `TaskManager::run` takes a plain `fn(&Package) -> Result<()>`, so
+    // `--locked`/`--frozen` can't be threaded into `update_plugin` through a
+    // capturing closure; stash them here for the duration of the run instead.
+    static ref LOCKED: AtomicBool = AtomicBool::new(false);
+    static ref FROZEN: AtomicBool = AtomicBool::new(false);
+}
+
 const USAGE: &str = "
 Update plugins.
 
@@ -19,6 +30,11 @@ Options:
                             configrations.
     -s, --skip SKIP         Comma separated list of plugins to skip
     -j, --threads THREADS   Update plugins concurrently
+        --locked            Check out the revision pinned in the lockfile
+                            instead of pulling the latest changes, failing
+                            loudly if a plugin has no pinned revision or the
+                            pinned revision can no longer be checked out
+        --frozen            Like --locked, but also refuse any network access
     -h, --help              Display this message
 ";
 
@@ -28,6 +44,8 @@ struct UpdateArgs {
     flag_threads: Option<usize>,
     flag_packfile: Option<bool>,
     flag_skip: String,
+    flag_locked: bool,
+    flag_frozen: bool,
 }
 
 pub fn execute(args: &[String]) {
@@ -55,7 +73,10 @@ pub fn execute(args: &[String]) {
         .filter(|x| !x.is_empty())
         .collect();
 
-    if let Err(e) = update_plugins(&args.arg_plugin, threads, &skip) {
+    let frozen = args.flag_frozen;
+    let locked = args.flag_locked || frozen;
+
+    if let Err(e) = update_plugins(&args.arg_plugin, threads, &skip, locked, frozen) {
         die!("Err: {}", e);
     }
 }
@@ -70,7 +91,15 @@ fn update_packfile() -> Result<()> {
     Ok(())
 }
 
-fn update_plugins(plugins: &[String], threads: usize, skip: &[String]) -> Result<()> {
+fn update_plugins(plugins: &[String],
+                   threads: usize,
+                   skip: &[String],
+                   locked: bool,
+                   frozen: bool)
+                   -> Result<()> {
+    LOCKED.store(locked, Ordering::SeqCst);
+    FROZEN.store(frozen, Ordering::SeqCst);
+
     let mut packs = package::fetch()?;
 
     let mut manager = TaskManager::new(threads);
@@ -83,6 +112,8 @@ fn update_plugins(plugins: &[String], threads: usize, skip: &[String]) -> Result
             manager.add(pack.clone());
         }
     } else {
+        suggest::check_known(plugins, &packs);
+
         for pack in packs.iter().filter(|x| plugins.contains(&x.name)) {
             manager.add(pack.clone());
         }
@@ -102,10 +133,49 @@ fn update_plugins(plugins: &[String], threads: usize, skip: &[String]) -> Result
 fn update_plugin(pack: &Package) -> Result<()> {
     let path = pack.path();
     if !path.is_dir() {
-        Err(Error::PluginNotInstalled)
+        return Err(Error::PluginNotInstalled);
     } else if pack.local {
-        Err(Error::SkipLocal)
+        return Err(Error::SkipLocal);
+    }
+
+    let locked = LOCKED.load(Ordering::SeqCst);
+    let frozen = FROZEN.load(Ordering::SeqCst);
+
+    if locked {
+        let pinned = package::fetch_lock()
+            .unwrap_or_else(Vec::new)
+            .into_iter()
+            .find(|p| p.name == pack.name)
+            .and_then(|p| p.revision);
+
+        let rev = match pinned {
+            Some(rev) => rev,
+            None => die!("{} has no pinned revision in the lockfile; run `pack update` \
+                           without --locked first", pack.name),
+        };
+
+        if frozen {
+            git::checkout_offline(&pack.name, &path, &rev)?;
+        } else {
+            git::checkout(&pack.name, &path, &rev)?;
+        }
+
+        // A checkout can silently fall short of the requested commit
+        // (e.g. a frozen checkout of a revision never fetched locally);
+        // confirm we actually landed on the pin rather than leaving the
+        // plugin on whatever commit it happened to be on before.
+        let actual = git::head(&path)?;
+        if actual != rev {
+            die!("{} has drifted from its pinned revision: expected {}, found {}",
+                 pack.name, rev, actual);
+        }
+
+        Ok(())
     } else {
-        git::update(&pack.name, &path)
+        git::update(&pack.name, &path)?;
+        let rev = git::head(&path)?;
+        let mut locked_pack = pack.clone();
+        locked_pack.set_revision(&rev);
+        package::update_lock_entry(&locked_pack)
     }
 }
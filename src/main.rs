@@ -0,0 +1,70 @@
+#[macro_use]
+extern crate lazy_static;
+extern crate docopt;
+extern crate rustc_serialize;
+extern crate yaml_rust;
+extern crate num_cpus;
+
+macro_rules! die {
+    ($($arg:tt)*) => {{
+        eprintln!($($arg)*);
+        ::std::process::exit(1);
+    }}
+}
+
+mod error;
+mod package;
+mod git;
+mod task;
+mod suggest;
+mod alias;
+mod cmd;
+
+use std::env;
+
+pub use error::{Error, Result};
+
+const USAGE: &str = "
+pack, a minimal vim8 plugin manager.
+
+Usage:
+    pack <command> [<args>...]
+    pack -h | --help
+
+Commands:
+    update          Update installed plugins
+    uninstall       Uninstall plugins
+    sync            Reconcile installed plugins against a manifest file
+";
+
+const SUBCOMMANDS: &[&str] = &["update", "uninstall", "sync"];
+
+fn is_builtin(cmd: &str) -> bool {
+    SUBCOMMANDS.contains(&cmd)
+}
+
+fn main() {
+    let argv: Vec<String> = env::args().skip(1).collect();
+
+    // Aliases are expanded before dispatch, but `resolve` stops as soon as
+    // the first word is a built-in, so a built-in subcommand always wins
+    // over a user alias of the same name.
+    let mut argv = alias::resolve(argv, is_builtin).into_iter();
+
+    let cmd = match argv.next() {
+        Some(cmd) => cmd,
+        None => {
+            println!("{}", USAGE);
+            return;
+        }
+    };
+    let rest: Vec<String> = argv.collect();
+
+    match cmd.as_str() {
+        "update" => cmd::update::execute(&rest),
+        "uninstall" => cmd::uninstall::execute(&rest),
+        "sync" => cmd::sync::execute(&rest),
+        "-h" | "--help" => println!("{}", USAGE),
+        other => die!("no such subcommand: `{}`", other),
+    }
+}
@@ -1,8 +1,9 @@
 use std::fs::{self, File};
 use std::io::{Read, Write};
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fmt;
+use std::sync::Mutex;
 
 use {Result, Error};
 
@@ -24,7 +25,12 @@ lazy_static! {
     static ref PACK_DIR: PathBuf = (*BASE_DIR).join("pack");
     pub static ref PACK_CONFIG_DIR: PathBuf = (*BASE_DIR).join(".pack");
     static ref PACK_FILE: PathBuf = (*PACK_CONFIG_DIR).join("packfile");
+    static ref LOCK_FILE: PathBuf = (*PACK_CONFIG_DIR).join("packfile.lock");
     pub static ref PACK_PLUGIN_FILE: PathBuf = (*BASE_DIR).join("plugin").join("__pack.vim");
+    /// Serializes read-modify-write access to the lockfile: `update_lock_entry`
+    /// is called from `TaskManager` worker threads and must not let two
+    /// threads race a fetch/save round-trip against the same file.
+    static ref LOCK_FILE_GUARD: Mutex<()> = Mutex::new(());
 }
 
 #[derive(Debug, Clone)]
@@ -34,6 +40,9 @@ pub struct Package {
     pub opt: bool,
     /// Load this package on this command
     pub load_command: Option<String>,
+    /// Git commit SHA this package was last updated to, as recorded in the
+    /// lockfile
+    pub revision: Option<String>,
 }
 
 impl Package {
@@ -43,6 +52,7 @@ impl Package {
             category: category.to_string(),
             opt: opt,
             load_command: None,
+            revision: None,
         }
     }
 
@@ -62,17 +72,23 @@ impl Package {
         self.load_command = Some(cmd.to_string())
     }
 
+    pub fn set_revision(&mut self, rev: &str) {
+        self.revision = Some(rev.to_string())
+    }
+
     pub fn from_yaml(doc: &Yaml) -> Result<Package> {
         let name = doc["name"].as_str().map(|s| s.to_string()).ok_or(Error::Format)?;
         let opt = doc["opt"].as_bool().ok_or(Error::Format)?;
         let category = doc["category"].as_str().map(|s| s.to_string()).ok_or(Error::Format)?;
         let cmd = doc["on"].as_str().map(|s| s.to_string());
+        let rev = doc["rev"].as_str().map(|s| s.to_string());
 
         Ok(Package {
             name: name,
             category: category,
             opt: opt,
             load_command: cmd,
+            revision: rev,
         })
     }
 
@@ -84,6 +100,9 @@ impl Package {
         if let Some(ref c) = self.load_command {
             doc.insert(Yaml::from_str("on"), Yaml::from_str(c));
         }
+        if let Some(ref r) = self.revision {
+            doc.insert(Yaml::from_str("rev"), Yaml::from_str(r));
+        }
         Yaml::Hash(doc)
     }
 
@@ -135,21 +154,23 @@ pub fn fetch() -> Option<Vec<Package>> {
         return None;
     }
 
+    fetch_from(&PACK_FILE).ok()
+}
+
+/// Load a packfile-shaped YAML document from an arbitrary path, e.g. a
+/// user-authored manifest passed to `pack sync`.
+pub fn fetch_from(path: &Path) -> Result<Vec<Package>> {
     let mut data = String::new();
-    File::open(&*PACK_FILE)
-        .expect("Fail to open packfile")
-        .read_to_string(&mut data)
-        .expect("Fail to read packfile");
+    File::open(path)?.read_to_string(&mut data)?;
     let docs = YamlLoader::load_from_str(&data).expect("Unexpected packfile format");
 
     if docs.is_empty() {
-        None
-    } else {
-        docs[0].as_vec().map(|doc| {
-            doc.iter()
-                .map(|d| Package::from_yaml(d).expect("Invalid format"))
-                .collect::<Vec<Package>>()
-        })
+        return Ok(Vec::new());
+    }
+
+    match docs[0].as_vec() {
+        Some(doc) => doc.iter().map(Package::from_yaml).collect(),
+        None => Ok(Vec::new()),
     }
 }
 
@@ -171,6 +192,48 @@ pub fn save(mut packs: Vec<Package>) -> Result<()> {
     Ok(())
 }
 
+/// Load the generated lockfile, which pins each plugin to the git revision
+/// it was last successfully installed or updated to.
+pub fn fetch_lock() -> Option<Vec<Package>> {
+    if !LOCK_FILE.is_file() {
+        return None;
+    }
+
+    fetch_from(&LOCK_FILE).ok()
+}
+
+pub fn save_lock(packs: &[Package]) -> Result<()> {
+    let mut packs = packs.to_vec();
+    packs.sort_by(|a, b| a.name.cmp(&b.name));
+    let packs = packs.into_iter().map(|e| e.into_yaml()).collect::<Vec<Yaml>>();
+    let doc = Yaml::Array(packs);
+    let mut out = String::new();
+    {
+        let mut emitter = YamlEmitter::new(&mut out);
+        emitter.dump(&doc)?;
+    }
+    if !PACK_CONFIG_DIR.is_dir() {
+        fs::create_dir_all(&*PACK_CONFIG_DIR)?;
+    }
+    let mut f = File::create(&*LOCK_FILE)?;
+    f.write(PACKFILE_HEADER)?;
+    f.write(out.as_bytes())?;
+    Ok(())
+}
+
+/// Record (or update) a single plugin's pinned revision in the lockfile.
+///
+/// Safe to call concurrently from multiple `TaskManager` worker threads:
+/// the fetch/retain/push/save round-trip is serialized on `LOCK_FILE_GUARD`
+/// so concurrent updates can't clobber each other or interleave writes.
+pub fn update_lock_entry(pack: &Package) -> Result<()> {
+    let _guard = LOCK_FILE_GUARD.lock().unwrap();
+    let mut locked = fetch_lock().unwrap_or_else(Vec::new);
+    locked.retain(|p| p.name != pack.name);
+    locked.push(pack.clone());
+    save_lock(&locked)
+}
+
 // #[test]
 // fn test_fetch() {
 //     env::set_var("VIM_CONFIG_PATH", "./test");
@@ -0,0 +1,81 @@
+//! "Did you mean..." suggestions for plugin name typos, the way cargo
+//! suggests near-miss subcommands.
+
+use package::Package;
+
+/// Two-row dynamic-programming edit distance between `a` and `b`.
+pub fn lev_distance(a: &str, b: &str) -> usize {
+    let a_len = a.chars().count();
+    let b_len = b.chars().count();
+
+    if a_len == 0 {
+        return b_len;
+    }
+    if b_len == 0 {
+        return a_len;
+    }
+
+    let mut prev: Vec<usize> = (0..a_len + 1).collect();
+    let mut cur: Vec<usize> = vec![0; a_len + 1];
+
+    for (i, cb) in b.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, ca) in a.chars().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            cur[j + 1] = ::std::cmp::min(prev[j + 1] + 1,
+                                          ::std::cmp::min(cur[j] + 1, prev[j] + cost));
+        }
+        ::std::mem::swap(&mut prev, &mut cur);
+    }
+
+    prev[a_len]
+}
+
+/// Find the closest known plugin to `typo` among `packs`, matching both the
+/// full `user/repo` name and just the `repo` component so e.g. `foo.vim`
+/// finds `user/foo.vim` by distance. Always returns the owning pack's full
+/// `name` (the only form callers can act on), never the bare `repo`
+/// component used for comparison. Returns `None` if nothing is close enough
+/// to be a useful suggestion.
+pub fn suggest<'a>(typo: &str, packs: &'a [Package]) -> Option<&'a str> {
+    let threshold = ::std::cmp::max(typo.len() / 3, 2);
+
+    let mut best: Option<(&str, usize)> = None;
+    for pack in packs {
+        let (_, repo) = pack.repo();
+        for candidate in &[pack.name.as_str(), repo] {
+            if candidate.is_empty() {
+                continue;
+            }
+            let dist = lev_distance(typo, candidate);
+            let is_better = match best {
+                Some((_, best_dist)) => dist < best_dist,
+                None => true,
+            };
+            if is_better {
+                best = Some((pack.name.as_str(), dist));
+            }
+        }
+    }
+
+    match best {
+        Some((name, dist)) if dist <= threshold => Some(name),
+        _ => None,
+    }
+}
+
+/// Check that every requested plugin name is installed, dying with a
+/// "did you mean" suggestion (or a plain not-found message) on the first
+/// one that isn't. Shared by `uninstall`/`update` so both commands report
+/// unknown plugin names the same way.
+pub fn check_known(plugins: &[String], packs: &[Package]) {
+    for name in plugins {
+        if packs.iter().any(|p| &p.name == name) {
+            continue;
+        }
+        match suggest(name, packs) {
+            Some(hint) => die!("no plugin named `{}` is installed; did you mean `{}`?", name, hint),
+            None => die!("no plugin named `{}` is installed", name),
+        }
+    }
+}